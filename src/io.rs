@@ -0,0 +1,68 @@
+use std::fs::File;
+use std::path::Path;
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+use memmap2::Mmap;
+use std_semaphore::Semaphore;
+
+use crate::error::Error;
+
+/// Bounds how many table files may be memory-mapped and held resident at
+/// once, per `Options::max_open_files`. Unlike a plain fd-open budget, the
+/// slot `open_table_mmap` acquires is held by the returned
+/// [`OpenFilePermit`] for as long as the mapping stays resident (embedded
+/// in the `ScTableCache` it backs), not just for the duration of the
+/// `open`/`mmap` syscalls -- the same "one slot per live thing" shape as
+/// `table::cache::TableCacheManager`'s memory-tier quota.
+pub(crate) struct IOManager {
+    sem: Semaphore,
+}
+
+impl IOManager {
+    pub(crate) fn new(max_open_files: usize) -> Self {
+        Self { sem: Semaphore::new(max_open_files as isize) }
+    }
+
+    /// Blocks until a resident-mapping slot is free, then memory-maps
+    /// `path`. Drop the returned [`OpenFilePermit`] once the mapping is no
+    /// longer needed to free the slot for another table.
+    pub(crate) fn open_table_mmap(&self, path: &Path) -> Result<(Arc<Mmap>, OpenFilePermit), Error> {
+        self.sem.acquire();
+        match File::open(path).and_then(|file| unsafe { Mmap::map(&file) }) {
+            Ok(mmap) => Ok((Arc::new(mmap), OpenFilePermit::new(self))),
+            Err(e) => {
+                self.sem.release();
+                Err(Error::sc_table_corrupt(format!("failed to open table file {:?}: {}", path, e)))
+            }
+        }
+    }
+
+    fn release_slot(&self) {
+        self.sem.release()
+    }
+}
+
+/// RAII hold on one of `IOManager`'s `max_open_files` slots, released on
+/// drop. Uses the same unsafe `NonNull`-based design as
+/// `table::cache::CacheQuota` rather than a borrowed lifetime, so it can be
+/// embedded in `ScTableCache` without forcing a lifetime parameter onto it
+/// (and everything that stores one).
+///
+/// Warning: make sure every `OpenFilePermit` is dropped before its
+/// `IOManager` drops.
+pub(crate) struct OpenFilePermit {
+    io_manager: NonNull<IOManager>,
+}
+
+impl OpenFilePermit {
+    fn new(io_manager: &IOManager) -> Self {
+        Self { io_manager: unsafe { NonNull::new_unchecked(io_manager as *const IOManager as *mut _) } }
+    }
+}
+
+impl Drop for OpenFilePermit {
+    fn drop(&mut self) {
+        unsafe { self.io_manager.as_ref().release_slot() }
+    }
+}
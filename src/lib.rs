@@ -14,6 +14,7 @@ mod partition;
 mod io;
 
 pub use table::tablefmt;
+pub use table::sctable::ScTableFile;
 
 pub trait Comparator {
     fn compare(lhs: &[u8], rhs: &[u8]) -> Ordering;
@@ -27,6 +28,16 @@ impl Comparator for DefaultComparator {
     }
 }
 
+/// Codec applied to a table's data region at flush time; the catalog
+/// region is always stored uncompressed. Stored per-table in a small
+/// header at the start of the data region so tables written under
+/// different `Options` remain independently readable.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CompressionType {
+    None,
+    Lz4,
+}
+
 pub struct Options {
     pub db_name: String,
     pub cache_count: usize,
@@ -36,6 +47,8 @@ pub struct Options {
     pub table_size: usize,
     pub key_size_max: usize,
     pub value_size_max: usize,
+    pub compression: CompressionType,
+    pub disk_cache_bytes: usize,
 }
 
 impl Options {
@@ -56,9 +69,23 @@ impl Options {
             table_size,
             key_size_max,
             value_size_max,
+            compression: CompressionType::None,
+            disk_cache_bytes: 0,
         }
     }
 
+    pub fn with_compression(mut self, compression: CompressionType) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Size of the second, disk-backed cache tier that `TableCacheManager`
+    /// demotes evicted tables into. `0` (the default) disables the tier.
+    pub fn with_disk_cache_bytes(mut self, disk_cache_bytes: usize) -> Self {
+        self.disk_cache_bytes = disk_cache_bytes;
+        self
+    }
+
     fn level_size(&self, level: usize) -> usize {
         self.level0_size * self.size_factor.pow(level as u32)
     }
@@ -82,15 +109,67 @@ impl<'a, Comp: 'static + Comparator> ScottDB<'a, Comp> {
     pub fn new(options: Options) -> Self {
         let cache_count = options.cache_count;
         let max_open_files = options.max_open_files;
+        let cache_manager = TableCacheManager::new(cache_count, &options.db_name, options.disk_cache_bytes);
         Self {
             phantom: PhantomData,
             options,
             seq: AtomicU64::new(0),
             partitions: VecDeque::new(),
-            cache_manager: TableCacheManager::new(cache_count),
+            cache_manager,
             io_manager: IOManager::new(max_open_files),
         }
     }
+
+    /// Metadata for every table currently live across all partitions, for
+    /// debugging compaction/level balance and capacity planning. This only
+    /// consults the in-memory cache tier: a table that is live but not
+    /// presently resident in memory is skipped rather than promoted from
+    /// the disk tier or re-opened, so `live_files` never blocks on the
+    /// cache manager's quota semaphore or evicts another live table just to
+    /// answer an introspection query.
+    pub fn live_files(&self) -> Vec<LiveFileMeta> {
+        let mut out = Vec::new();
+        for partition in &self.partitions {
+            for (level, table_file) in partition.live_tables() {
+                let cache = match self.cache_manager.peek_cache(table_file) {
+                    Some(cache) => cache,
+                    None => continue,
+                };
+                let entry_count = cache.catalog_size();
+                if entry_count == 0 {
+                    continue
+                }
+                let (_, smallest_key, _) = cache.nth_item(0);
+                let (_, largest_key, _) = cache.nth_item(entry_count - 1);
+                out.push(LiveFileMeta {
+                    table_file,
+                    level,
+                    smallest_key: smallest_key.to_vec(),
+                    largest_key: largest_key.to_vec(),
+                    entry_count,
+                    size_bytes: cache.weight(),
+                });
+            }
+        }
+        out
+    }
+
+    /// Sum of the resident table data+catalog bytes currently held by the
+    /// memory cache tier. Does not count tables demoted to the disk tier,
+    /// since those no longer occupy heap memory.
+    pub fn approximate_memory_usage(&self) -> usize {
+        self.cache_manager.resident_bytes()
+    }
+}
+
+/// One table's metadata as reported by [`ScottDB::live_files`].
+pub struct LiveFileMeta {
+    pub table_file: ScTableFile,
+    pub level: usize,
+    pub smallest_key: Vec<u8>,
+    pub largest_key: Vec<u8>,
+    pub entry_count: usize,
+    pub size_bytes: usize,
 }
 
 #[cfg(test)]
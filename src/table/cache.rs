@@ -1,19 +1,127 @@
+use std::cmp::Ordering as KeyOrdering;
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::ptr::NonNull;
+use std::path::PathBuf;
+use std::fs;
 use std_semaphore::Semaphore;
 
 use lru::LruCache;
 use crc::crc32;
+use memmap2::Mmap;
 
 use crate::table::sctable::ScTableFile;
 
 use crate::table::tablefmt::{TABLE_MIN_SIZE, TABLE_MAGIC_SIZE, TABLE_MAGIC, TABLE_CATALOG_ITEM_SIZE,
                              TABLE_HEAD_SIZE, TABLE_MAX_SIZE, TABLE_DELETION_BITMASK};
-use crate::encode::{encode_fixed32_ret, decode_fixed32, decode_fixed64, encode_fixed64_ret};
+use crate::encode::{encode_fixed32_ret, decode_fixed32, encode_fixed64_ret};
 use crate::error::Error;
-use crate::Comparator;
+use crate::io::{IOManager, OpenFilePermit};
+use crate::{Comparator, CompressionType};
 use crate::partition::{InternalKey, UserKey};
 
+/// Prefix written ahead of the (possibly compressed) data region: the
+/// codec in use plus the uncompressed length, so `from_raw` knows how
+/// large a buffer to reserve before inflating.
+const TABLE_DATA_CODEC_HEADER_SIZE: usize = 8;
+
+fn data_codec_byte(compression: CompressionType) -> u8 {
+    match compression {
+        CompressionType::None => 0,
+        CompressionType::Lz4 => 1,
+    }
+}
+
+/// Compresses `raw_data` per `compression`, prefixing it with the codec
+/// byte + uncompressed length that `decode_data_region` expects.
+pub(crate) fn encode_data_region(raw_data: &[u8], compression: CompressionType) -> Vec<u8> {
+    let mut out = Vec::with_capacity(TABLE_DATA_CODEC_HEADER_SIZE + raw_data.len());
+    out.push(data_codec_byte(compression));
+    out.extend_from_slice(&[0u8; 3]);
+    out.extend_from_slice(&encode_fixed32_ret(raw_data.len() as u32));
+    match compression {
+        CompressionType::None => out.extend_from_slice(raw_data),
+        CompressionType::Lz4 => out.extend_from_slice(&lz4_flex::compress(raw_data)),
+    }
+    out
+}
+
+/// Result of parsing a data region's codec header: either the payload is
+/// stored verbatim (`Identity`, eligible for a zero-copy mmap view at
+/// `payload_offset` relative to the region start) or it had to be
+/// inflated into an owned buffer.
+enum DecodedDataRegion {
+    Identity { payload_offset: usize, len: usize },
+    Owned(Vec<u8>),
+}
+
+fn decode_data_region(region: &[u8]) -> Result<DecodedDataRegion, Error> {
+    if region.len() < TABLE_DATA_CODEC_HEADER_SIZE {
+        return Err(Error::sc_table_corrupt("data region missing codec header".into()))
+    }
+    let codec = region[0];
+    let uncompressed_len = decode_fixed32(&region[4..8]) as usize;
+    let payload = &region[TABLE_DATA_CODEC_HEADER_SIZE..];
+
+    // Bound the claimed uncompressed size before it drives an allocation,
+    // whether ours (the identity slice below) or `lz4_flex`'s internal
+    // decompression buffer.
+    if uncompressed_len > TABLE_MAX_SIZE {
+        return Err(Error::sc_table_corrupt("declared uncompressed data size exceeds table size limit".into()))
+    }
+
+    match codec {
+        0 => {
+            if TABLE_DATA_CODEC_HEADER_SIZE + uncompressed_len > region.len() {
+                return Err(Error::sc_table_corrupt("incorrect data region size".into()))
+            }
+            Ok(DecodedDataRegion::Identity { payload_offset: TABLE_DATA_CODEC_HEADER_SIZE, len: uncompressed_len })
+        }
+        1 => lz4_flex::decompress(payload, uncompressed_len)
+            .map(DecodedDataRegion::Owned)
+            .map_err(|_| Error::sc_table_corrupt("corrupt lz4 data region".into())),
+        _ => Err(Error::sc_table_corrupt("unknown data region codec".into())),
+    }
+}
+
+/// Copies `slice` into a freshly allocated `Vec` via a fallible reservation,
+/// so a corrupted or adversarial length prefix returns `Error::sc_table_corrupt`
+/// instead of aborting the process when the claimed size is too large to
+/// allocate.
+fn try_clone_slice<T: Copy>(slice: &[T]) -> Result<Vec<T>, Error> {
+    let mut out = Vec::new();
+    out.try_reserve_exact(slice.len())
+        .map_err(|_| Error::sc_table_corrupt("allocation failed while copying table region".into()))?;
+    out.extend_from_slice(slice);
+    Ok(out)
+}
+
+/// Bounds-checked sub-slice, for parsers walking a flat byte buffer with a
+/// running `pos` cursor: returns `Error::sc_table_corrupt` instead of
+/// panicking when `pos + len` runs past the end of `bytes`, e.g. a torn or
+/// truncated disk-cache file.
+fn take_bytes(bytes: &[u8], pos: usize, len: usize) -> Result<&[u8], Error> {
+    bytes.get(pos..pos + len).ok_or_else(|| Error::sc_table_corrupt("truncated disk cache entry".into()))
+}
+
+/// Declaring `ScTableCatalogItem` as `bytemuck::Pod` asserts that reading its
+/// fields as native integers matches `encode_fixed32`/`encode_fixed64`'s
+/// on-disk byte order -- true only on a little-endian host. Rather than
+/// silently producing wrong offsets/lengths on a big-endian target, the
+/// `compile_error!` below refuses the build there; `cfg_attr` keeps the
+/// derive itself little-endian-only so it can never be reinstated by
+/// accident.
+#[cfg(not(target_endian = "little"))]
+compile_error!("ScTableCatalogItem's zero-copy bytemuck cast assumes a little-endian host matching encode_fixed32/encode_fixed64's on-disk byte order; this crate does not yet support big-endian targets");
+
+/// `repr(C, packed)` so that a validated catalog byte range can be
+/// reinterpreted in place via `bytemuck::try_cast_slice` instead of being
+/// decoded item by item; `packed` drops the struct's alignment requirement
+/// to 1, since the catalog region is only guaranteed to be 4-byte aligned
+/// within the table file.
+#[repr(C, packed)]
+#[derive(Copy, Clone)]
+#[cfg_attr(target_endian = "little", derive(bytemuck::Pod, bytemuck::Zeroable))]
 pub(crate) struct ScTableCatalogItem {
     pub(crate) key_seq: u64,
     pub(crate) key_off: u32,
@@ -34,94 +142,510 @@ impl ScTableCatalogItem {
         dest.extend_from_slice(&encode_fixed32_ret(self.value_off));
         dest.extend_from_slice(&encode_fixed32_ret(self.value_len));
     }
+}
 
-    pub(crate) fn deserialize(from: &[u8]) -> Self {
-        debug_assert_eq!(from.len(), TABLE_CATALOG_ITEM_SIZE);
-        Self {
-            key_seq: decode_fixed64(&from[0..8]),
-            key_off: decode_fixed32(&from[8..12]),
-            key_len: decode_fixed32(&from[12..16]),
-            value_off: decode_fixed32(&from[16..20]),
-            value_len: decode_fixed32(&from[20..24]),
+/// Appended after the data region and before the trailing magic: a
+/// length-prefixed, CRC32-checksummed bitmap built by
+/// [`TableBloomFilter::build`] at flush time. `bloom_len == 0` means the
+/// table was written without a filter (or by an older version) and
+/// `ScTableCache::get` skips the membership test.
+const TABLE_BLOOM_FOOTER_SIZE: usize = 8;
+
+/// Roughly 1% false positive rate at `BITS_PER_KEY == 10` and
+/// `NUM_HASHES == 7`, per the standard Bloom filter sizing formula.
+pub(crate) struct TableBloomFilter {
+    bits: Vec<u8>,
+    num_hashes: u32,
+}
+
+impl TableBloomFilter {
+    const BITS_PER_KEY: usize = 10;
+    const NUM_HASHES: u32 = 7;
+
+    pub(crate) fn build<'a>(keys: impl Iterator<Item = &'a [u8]>) -> Self {
+        let keys: Vec<&[u8]> = keys.collect();
+        let num_bits = std::cmp::max(keys.len() * Self::BITS_PER_KEY, 64);
+        let mut filter = Self { bits: vec![0u8; (num_bits + 7) / 8], num_hashes: Self::NUM_HASHES };
+        for key in keys {
+            filter.insert(key);
+        }
+        filter
+    }
+
+    fn hash_pair(key: &[u8]) -> (u32, u32) {
+        let h1 = crc32::checksum_ieee(key);
+        let h2 = crc32::checksum_castagnoli(key);
+        // A zero second hash would collapse every probe onto the same bit.
+        (h1, if h2 == 0 { 1 } else { h2 })
+    }
+
+    fn bit_index(&self, h1: u32, h2: u32, i: u32) -> usize {
+        let num_bits = self.bits.len() * 8;
+        (h1.wrapping_add(i.wrapping_mul(h2)) as usize) % num_bits
+    }
+
+    fn insert(&mut self, key: &[u8]) {
+        let (h1, h2) = Self::hash_pair(key);
+        for i in 0..self.num_hashes {
+            let bit = self.bit_index(h1, h2, i);
+            self.bits[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+
+    pub(crate) fn may_contain(&self, key: &[u8]) -> bool {
+        let (h1, h2) = Self::hash_pair(key);
+        for i in 0..self.num_hashes {
+            let bit = self.bit_index(h1, h2, i);
+            if self.bits[bit / 8] & (1 << (bit % 8)) == 0 {
+                return false
+            }
         }
+        true
+    }
+
+    pub(crate) fn serialize(&self, dest: &mut Vec<u8>) {
+        dest.extend_from_slice(&encode_fixed32_ret(self.bits.len() as u32));
+        dest.extend_from_slice(&encode_fixed32_ret(crc32::checksum_ieee(&self.bits)));
+        dest.extend_from_slice(&self.bits);
+    }
+
+    fn parse(raw: &[u8]) -> Result<Option<Self>, Error> {
+        let bloom_len = decode_fixed32(&raw[0..4]) as usize;
+        if bloom_len == 0 {
+            return Ok(None)
+        }
+        let bloom_crc = decode_fixed32(&raw[4..8]);
+        let bits_start = TABLE_BLOOM_FOOTER_SIZE;
+        if bits_start + bloom_len > raw.len() {
+            return Err(Error::sc_table_corrupt("incorrect bloom filter size".into()))
+        }
+        let bits = &raw[bits_start..bits_start + bloom_len];
+        if crc32::checksum_ieee(bits) != bloom_crc {
+            return Err(Error::sc_table_corrupt("incorrect bloom filter crc".into()))
+        }
+        Ok(Some(Self { bits: try_clone_slice(bits)?, num_hashes: Self::NUM_HASHES }))
     }
 }
 
-pub(crate) struct ScTableCache {
-    catalog: Vec<ScTableCatalogItem>,
-    data: Vec<u8>,
-    quota: CacheQuota
+/// Appended after the Bloom filter region and before the trailing magic:
+/// a length-prefixed, CRC32-checksummed SwissTable-style open-addressing
+/// index, built at flush time so `get` can serve equality lookups in
+/// O(1) instead of the sorted catalog's binary search. `index_len == 0`
+/// means the table was written without one and `get` falls back to the
+/// binary search.
+const TABLE_HASH_INDEX_FOOTER_SIZE: usize = 8;
+
+const HASH_INDEX_GROUP_SIZE: usize = 16;
+/// Control byte sentinels, SwissTable convention: the top bit set marks a
+/// non-full slot, distinguishing it from a 7-bit tag (always top-bit-clear).
+const HASH_INDEX_CTRL_EMPTY: u8 = 0b1111_1111;
+
+/// Open-addressing index from a key's hash to its catalog slot. `control`
+/// holds one byte per slot in groups of 16: either [`HASH_INDEX_CTRL_EMPTY`]
+/// or the low 7 bits of the key's hash (the "tag"). `slots` holds the
+/// matching catalog index. A lookup hashes the key, jumps to the slot's
+/// home group, and compares the tag against all 16 control bytes in the
+/// group at once (SSE2 where available) before confirming any match
+/// against the real key — same shape as Abseil/hashbrown's SwissTable.
+pub(crate) struct TableHashIndex {
+    control: Vec<u8>,
+    slots: Vec<u32>,
+    num_groups: usize,
 }
 
-impl ScTableCache {
-    pub(crate) fn from_raw(raw: &[u8], quota: CacheQuota) -> Result<ScTableCache, Error> {
-        if raw.len() < TABLE_MIN_SIZE {
-            return Err(Error::sc_table_corrupt("too small to be a table file".into()))
-        } else if raw.len() > TABLE_MAX_SIZE {
-            return Err(Error::sc_table_corrupt("too large to be a table file".into()))
+impl TableHashIndex {
+    /// `keys` must yield catalog entries in catalog order, i.e. `nth` key
+    /// corresponds to catalog index `n` — the same order [`ScTableCache`]
+    /// builds its catalog in at flush time.
+    pub(crate) fn build<'a>(keys: impl ExactSizeIterator<Item = &'a [u8]>) -> Self {
+        let n = keys.len();
+        // ~87.5% max load factor, same target as hashbrown/abseil.
+        let num_groups = std::cmp::max(1, (n * 8 / 7 / HASH_INDEX_GROUP_SIZE) + 1);
+        let num_slots = num_groups * HASH_INDEX_GROUP_SIZE;
+        let mut control = vec![HASH_INDEX_CTRL_EMPTY; num_slots];
+        let mut slots = vec![0u32; num_slots];
+
+        for (catalog_idx, key) in keys.enumerate() {
+            let (mut group, tag) = Self::hash_parts(key, num_groups);
+            loop {
+                let base = group * HASH_INDEX_GROUP_SIZE;
+                if let Some(slot) = (0..HASH_INDEX_GROUP_SIZE).find(|&s| control[base + s] == HASH_INDEX_CTRL_EMPTY) {
+                    control[base + slot] = tag;
+                    slots[base + slot] = catalog_idx as u32;
+                    break
+                }
+                group = (group + 1) % num_groups;
+            }
+        }
+
+        Self { control, slots, num_groups }
+    }
+
+    fn hash_parts(key: &[u8], num_groups: usize) -> (usize, u8) {
+        let h = crc32::checksum_ieee(key);
+        (((h >> 7) as usize) % num_groups, (h & 0x7f) as u8)
+    }
+
+    /// Looks up `key`'s catalog index. `confirm` is called with each
+    /// tag-matching candidate to check the real key (tags collide); the
+    /// index itself doesn't hold key bytes, only catalog positions.
+    pub(crate) fn lookup(&self, key: &[u8], mut confirm: impl FnMut(u32) -> bool) -> Option<u32> {
+        let (mut group, tag) = Self::hash_parts(key, self.num_groups);
+        for _ in 0..self.num_groups {
+            let base = group * HASH_INDEX_GROUP_SIZE;
+            let ctrl_group = &self.control[base..base + HASH_INDEX_GROUP_SIZE];
+            for slot in match_group(ctrl_group, tag) {
+                let idx = self.slots[base + slot];
+                if confirm(idx) {
+                    return Some(idx)
+                }
+            }
+            if ctrl_group.iter().any(|&c| c == HASH_INDEX_CTRL_EMPTY) {
+                // An empty slot in the probe sequence means the key, if
+                // present, would have been inserted here or earlier.
+                return None
+            }
+            group = (group + 1) % self.num_groups;
         }
+        None
+    }
 
-        if &raw[raw.len()-TABLE_MAGIC_SIZE .. raw.len()] != TABLE_MAGIC {
-            return Err(Error::sc_table_corrupt("incorrect table magic".into()))
+    pub(crate) fn serialize(&self, dest: &mut Vec<u8>) {
+        let mut payload = Vec::with_capacity(4 + self.control.len() + self.slots.len() * 4);
+        payload.extend_from_slice(&encode_fixed32_ret(self.num_groups as u32));
+        payload.extend_from_slice(&self.control);
+        for &slot in &self.slots {
+            payload.extend_from_slice(&encode_fixed32_ret(slot));
         }
+        dest.extend_from_slice(&encode_fixed32_ret(payload.len() as u32));
+        dest.extend_from_slice(&encode_fixed32_ret(crc32::checksum_ieee(&payload)));
+        dest.extend_from_slice(&payload);
+    }
 
-        let kv_catalog_size = decode_fixed32(&raw[0..4]) as usize;
-        let data_size = decode_fixed32(&raw[4..8]) as usize;
+    fn parse(raw: &[u8]) -> Result<Option<Self>, Error> {
+        let index_len = decode_fixed32(&raw[0..4]) as usize;
+        if index_len == 0 {
+            return Ok(None)
+        }
+        let index_crc = decode_fixed32(&raw[4..8]);
+        let payload_start = TABLE_HASH_INDEX_FOOTER_SIZE;
+        if payload_start + index_len > raw.len() {
+            return Err(Error::sc_table_corrupt("incorrect hash index size".into()))
+        }
+        let payload = &raw[payload_start..payload_start + index_len];
+        if crc32::checksum_ieee(payload) != index_crc {
+            return Err(Error::sc_table_corrupt("incorrect hash index crc".into()))
+        }
 
-        if kv_catalog_size % TABLE_CATALOG_ITEM_SIZE != 0 {
-            return Err(Error::sc_table_corrupt("catalog size should be multiplication of 16".into()))
+        let num_groups = decode_fixed32(&payload[0..4]) as usize;
+        let num_slots = num_groups * HASH_INDEX_GROUP_SIZE;
+        if 4 + num_slots + num_slots * 4 != payload.len() {
+            return Err(Error::sc_table_corrupt("incorrect hash index layout".into()))
+        }
+        let control = try_clone_slice(&payload[4..4 + num_slots])?;
+        let mut slots = Vec::new();
+        slots.try_reserve_exact(num_slots)
+            .map_err(|_| Error::sc_table_corrupt("allocation failed while copying hash index slots".into()))?;
+        let mut pos = 4 + num_slots;
+        for _ in 0..num_slots {
+            slots.push(decode_fixed32(&payload[pos..pos + 4]));
+            pos += 4;
         }
 
-        if (kv_catalog_size + data_size + TABLE_MIN_SIZE) != raw.len() {
-            return Err(Error::sc_table_corrupt("incorrect table size".into()))
+        Ok(Some(Self { control, slots, num_groups }))
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn match_group(ctrl: &[u8], tag: u8) -> impl Iterator<Item = usize> {
+    debug_assert_eq!(ctrl.len(), HASH_INDEX_GROUP_SIZE);
+    use std::arch::x86_64::{_mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8, _mm_set1_epi8};
+    let mut mask = unsafe {
+        let group = _mm_loadu_si128(ctrl.as_ptr() as *const _);
+        let target = _mm_set1_epi8(tag as i8);
+        _mm_movemask_epi8(_mm_cmpeq_epi8(group, target)) as u32
+    };
+    std::iter::from_fn(move || {
+        if mask == 0 {
+            None
+        } else {
+            let slot = mask.trailing_zeros() as usize;
+            mask &= mask - 1;
+            Some(slot)
         }
+    })
+}
 
-        let kv_catalog_crc = decode_fixed32(&raw[8..12]);
-        let data_crc = decode_fixed32(&raw[12..16]);
+#[cfg(not(target_arch = "x86_64"))]
+fn match_group(ctrl: &[u8], tag: u8) -> impl Iterator<Item = usize> + '_ {
+    (0..ctrl.len()).filter(move |&slot| ctrl[slot] == tag)
+}
 
-        let kv_catalog = &raw[TABLE_HEAD_SIZE..TABLE_HEAD_SIZE+ kv_catalog_size];
-        let data = &raw[TABLE_HEAD_SIZE+ kv_catalog_size..TABLE_HEAD_SIZE+ kv_catalog_size +data_size];
+/// Backing storage for a table's decoded data region. `Mapped` is a
+/// zero-copy view into the table's `Mmap` (only possible when the region
+/// was written with `CompressionType::None`); `Owned` holds a buffer that
+/// had to be materialized, either because the caller handed us a
+/// transient `&[u8]` ([`ScTableCache::from_raw`]) or because the region
+/// was compressed and had to be inflated.
+enum TableData {
+    Mapped { mmap: Arc<Mmap>, offset: usize, len: usize },
+    Owned(Vec<u8>),
+}
 
-        if crc32::checksum_ieee(kv_catalog) != kv_catalog_crc {
-            return Err(Error::sc_table_corrupt("incorrect kv_catalog crc".into()))
+impl TableData {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            TableData::Mapped { mmap, offset, len } => &mmap[*offset..*offset + *len],
+            TableData::Owned(buf) => buf,
         }
+    }
+}
+
+/// The byte ranges of a table file once its header, size and magic have
+/// been validated, but before the (possibly compressed) data region has
+/// been decoded. Shared between [`ScTableCache::from_raw`] and
+/// [`ScTableCache::from_mmap`] so the two only differ in how they
+/// materialize the data region.
+struct ParsedRanges {
+    kv_catalog_range: std::ops::Range<usize>,
+    data_region_range: std::ops::Range<usize>,
+    bloom_range: std::ops::Range<usize>,
+    hash_index_range: std::ops::Range<usize>,
+}
+
+fn parse_ranges(raw: &[u8]) -> Result<ParsedRanges, Error> {
+    if raw.len() < TABLE_MIN_SIZE {
+        return Err(Error::sc_table_corrupt("too small to be a table file".into()))
+    } else if raw.len() > TABLE_MAX_SIZE {
+        return Err(Error::sc_table_corrupt("too large to be a table file".into()))
+    }
+
+    if &raw[raw.len()-TABLE_MAGIC_SIZE .. raw.len()] != TABLE_MAGIC {
+        return Err(Error::sc_table_corrupt("incorrect table magic".into()))
+    }
+
+    let kv_catalog_size = decode_fixed32(&raw[0..4]) as usize;
+    let data_size = decode_fixed32(&raw[4..8]) as usize;
+
+    // Bound the declared sizes against TABLE_MAX_SIZE before they drive any
+    // allocation further down: a torn or adversarial header can otherwise
+    // claim an arbitrary 32-bit region size regardless of how small `raw`
+    // actually is.
+    if kv_catalog_size > TABLE_MAX_SIZE || data_size > TABLE_MAX_SIZE {
+        return Err(Error::sc_table_corrupt("declared region size exceeds table size limit".into()))
+    }
+
+    if kv_catalog_size % TABLE_CATALOG_ITEM_SIZE != 0 {
+        return Err(Error::sc_table_corrupt("catalog size should be multiplication of 24".into()))
+    }
+
+    // Two variable-length footers precede the magic, innermost first: the
+    // hash index, then the Bloom filter. Both lengths come straight from the
+    // untrusted footer and, like kv_catalog_size/data_size above, are bounded
+    // against TABLE_MAX_SIZE before they are added into region sizes. The
+    // hash index region size is also checked against the bytes before it,
+    // so the `usize` subtraction locating the Bloom footer cannot underflow.
+    let hash_index_footer_end = raw.len() - TABLE_MAGIC_SIZE;
+    let hash_index_footer = &raw[hash_index_footer_end-TABLE_HASH_INDEX_FOOTER_SIZE .. hash_index_footer_end];
+    let hash_index_len = decode_fixed32(&hash_index_footer[0..4]) as usize;
+    if hash_index_len > TABLE_MAX_SIZE {
+        return Err(Error::sc_table_corrupt("declared hash index size exceeds table size limit".into()))
+    }
+    let hash_index_region_size = if hash_index_len == 0 { TABLE_HASH_INDEX_FOOTER_SIZE } else { TABLE_HASH_INDEX_FOOTER_SIZE + hash_index_len };
+    if hash_index_region_size + TABLE_BLOOM_FOOTER_SIZE > hash_index_footer_end {
+        return Err(Error::sc_table_corrupt("incorrect table size".into()))
+    }
+
+    let bloom_footer_end = hash_index_footer_end - hash_index_region_size;
+    let bloom_footer = &raw[bloom_footer_end-TABLE_BLOOM_FOOTER_SIZE .. bloom_footer_end];
+    let bloom_len = decode_fixed32(&bloom_footer[0..4]) as usize;
+    if bloom_len > TABLE_MAX_SIZE {
+        return Err(Error::sc_table_corrupt("declared bloom filter size exceeds table size limit".into()))
+    }
+    let bloom_region_size = if bloom_len == 0 { TABLE_BLOOM_FOOTER_SIZE } else { TABLE_BLOOM_FOOTER_SIZE + bloom_len };
+
+    if (kv_catalog_size + data_size + bloom_region_size + hash_index_region_size + TABLE_MIN_SIZE) != raw.len() {
+        return Err(Error::sc_table_corrupt("incorrect table size".into()))
+    }
+
+    let kv_catalog_crc = decode_fixed32(&raw[8..12]);
+    let data_crc = decode_fixed32(&raw[12..16]);
+
+    let kv_catalog_start = TABLE_HEAD_SIZE;
+    let data_start = kv_catalog_start + kv_catalog_size;
+    let bloom_start = data_start + data_size;
+    let hash_index_start = bloom_start + bloom_region_size;
+
+    let kv_catalog_range = kv_catalog_start..data_start;
+    let data_region_range = data_start..bloom_start;
+    let bloom_range = bloom_start..bloom_start + bloom_region_size;
+    let hash_index_range = hash_index_start..hash_index_start + hash_index_region_size;
 
-        if crc32::checksum_ieee(data) != data_crc {
-            return Err(Error::sc_table_corrupt("incorrect data crc".into()))
+    if crc32::checksum_ieee(&raw[kv_catalog_range.clone()]) != kv_catalog_crc {
+        return Err(Error::sc_table_corrupt("incorrect kv_catalog crc".into()))
+    }
+
+    if crc32::checksum_ieee(&raw[data_region_range.clone()]) != data_crc {
+        return Err(Error::sc_table_corrupt("incorrect data crc".into()))
+    }
+
+    Ok(ParsedRanges { kv_catalog_range, data_region_range, bloom_range, hash_index_range })
+}
+
+/// Reinterprets a validated catalog byte range as `&[ScTableCatalogItem]`
+/// via `bytemuck`, then walks it once to check each entry's key/value
+/// offsets against the data region — no per-item decode, no allocation.
+/// `kv_catalog.len() % TABLE_CATALOG_ITEM_SIZE == 0` (checked by
+/// `parse_ranges`) is exactly the slice-length invariant `try_cast_slice`
+/// needs, and `packed` repr means alignment can never reject the cast.
+fn catalog_items(kv_catalog: &[u8], data_len: usize) -> Result<&[ScTableCatalogItem], Error> {
+    let items: &[ScTableCatalogItem] = bytemuck::try_cast_slice(kv_catalog)
+        .map_err(|_| Error::sc_table_corrupt("catalog region is not a valid item array".into()))?;
+    for index in items {
+        let (value_off, key_off, key_len, value_len) =
+            (index.value_off, index.key_off, index.key_len, index.value_len);
+        if value_off & TABLE_DELETION_BITMASK != 0 {
+            continue
         }
+        // Widen to `usize` before adding: these offsets/lengths come straight
+        // from the untrusted catalog bytes, and a `u32 + u32` addition can
+        // overflow-panic on an adversarial (off, len) pair that a `usize`
+        // addition on a 64-bit host never will.
+        if key_off as usize + key_len as usize > data_len || value_off as usize + value_len as usize > data_len {
+            return Err(Error::sc_table_corrupt("incorrect key/value catalog data".into()))
+        }
+    }
+    Ok(items)
+}
+
+/// Backing storage for a table's catalog. `Mapped` reinterprets the
+/// validated catalog bytes straight out of the `Mmap` on every access — a
+/// pointer cast, not a copy — mirroring [`TableData::Mapped`]. `Owned`
+/// holds a copied `Vec` for inputs not tied to a long-lived mapping
+/// (`from_raw`, or a catalog rebuilt from the disk cache tier).
+enum CatalogData {
+    Mapped { mmap: Arc<Mmap>, offset: usize, count: usize },
+    Owned(Vec<ScTableCatalogItem>),
+}
 
-        let mut catalog_item = Vec::new();
-        for i in 0..kv_catalog_size / TABLE_CATALOG_ITEM_SIZE {
-            let base = i * TABLE_CATALOG_ITEM_SIZE;
-            let index =
-                ScTableCatalogItem::deserialize(&kv_catalog[base..base + TABLE_CATALOG_ITEM_SIZE]);
-            if index.value_off & TABLE_DELETION_BITMASK != 0 {
-            } else if (index.key_off + index.key_len) as usize > data.len()
-                      || (index.value_off + index.value_len) as usize > data.len() {
-                return Err(Error::sc_table_corrupt("incorrect key/value catalog data".into()))
+impl CatalogData {
+    fn as_slice(&self) -> &[ScTableCatalogItem] {
+        match self {
+            CatalogData::Mapped { mmap, offset, count } => {
+                let bytes = &mmap[*offset..*offset + *count * TABLE_CATALOG_ITEM_SIZE];
+                bytemuck::cast_slice(bytes)
             }
-            catalog_item.push(index)
+            CatalogData::Owned(items) => items,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            CatalogData::Mapped { count, .. } => *count,
+            CatalogData::Owned(items) => items.len(),
         }
+    }
+}
+
+pub(crate) struct ScTableCache {
+    catalog: CatalogData,
+    data: TableData,
+    bloom: Option<TableBloomFilter>,
+    hash_index: Option<TableHashIndex>,
+    quota: CacheQuota,
+    /// Held only by [`Self::open_mmap`]'s tables: releases one of
+    /// `IOManager`'s `max_open_files` slots when this cache entry is
+    /// dropped, so the budget tracks resident mappings, not just open
+    /// syscalls.
+    io_permit: Option<OpenFilePermit>,
+}
+
+impl ScTableCache {
+    /// Parses a table already fully read into memory. Both the catalog and
+    /// the data region are materialized into owned buffers since `raw`'s
+    /// lifetime isn't tied to the returned cache entry; prefer
+    /// [`Self::open_mmap`] for a zero-copy view of both.
+    pub(crate) fn from_raw(raw: &[u8], quota: CacheQuota) -> Result<ScTableCache, Error> {
+        let ranges = parse_ranges(raw)?;
+        let bloom = TableBloomFilter::parse(&raw[ranges.bloom_range.clone()])?;
+        let hash_index = TableHashIndex::parse(&raw[ranges.hash_index_range.clone()])?;
+        let data = match decode_data_region(&raw[ranges.data_region_range.clone()])? {
+            DecodedDataRegion::Identity { payload_offset, len } => {
+                let region = &raw[ranges.data_region_range.clone()];
+                TableData::Owned(try_clone_slice(&region[payload_offset..payload_offset + len])?)
+            }
+            DecodedDataRegion::Owned(buf) => TableData::Owned(buf),
+        };
+        let items = catalog_items(&raw[ranges.kv_catalog_range.clone()], data.as_slice().len())?;
+        let catalog = CatalogData::Owned(try_clone_slice(items)?);
+        Ok(Self { catalog, data, bloom, hash_index, quota, io_permit: None })
+    }
+
+    /// Parses a table backed by a memory mapping. When the data region was
+    /// written with `CompressionType::None`, both the catalog and
+    /// `key()`/`value()` slice straight out of `mmap` with no heap copy; a
+    /// compressed data region is still inflated into an owned buffer.
+    fn from_mmap(mmap: Arc<Mmap>, quota: CacheQuota) -> Result<ScTableCache, Error> {
+        let ranges = parse_ranges(&mmap)?;
+        let bloom = TableBloomFilter::parse(&mmap[ranges.bloom_range.clone()])?;
+        let hash_index = TableHashIndex::parse(&mmap[ranges.hash_index_range.clone()])?;
+        let data = match decode_data_region(&mmap[ranges.data_region_range.clone()])? {
+            DecodedDataRegion::Identity { payload_offset, len } => TableData::Mapped {
+                mmap: mmap.clone(),
+                offset: ranges.data_region_range.start + payload_offset,
+                len,
+            },
+            DecodedDataRegion::Owned(buf) => TableData::Owned(buf),
+        };
+        let count = catalog_items(&mmap[ranges.kv_catalog_range.clone()], data.as_slice().len())?.len();
+        let catalog = CatalogData::Mapped { mmap: mmap.clone(), offset: ranges.kv_catalog_range.start, count };
+        Ok(Self { catalog, data, bloom, hash_index, quota, io_permit: None })
+    }
 
-        Ok(Self { catalog: catalog_item, data: data.to_vec(), quota })
+    /// Opens `path` as a zero-copy, memory-mapped table through `io_manager`,
+    /// so `Options::max_open_files` actually bounds how many tables may be
+    /// mapped and held resident at once -- not just how many are parsed
+    /// per-call. Blocks until `io_manager` has a free slot, the same way
+    /// `TableCacheManager::acquire_quota` blocks for a free memory-tier
+    /// slot; the slot is held for as long as the returned cache stays alive.
+    pub(crate) fn open_mmap(path: &std::path::Path, io_manager: &IOManager, quota: CacheQuota) -> Result<ScTableCache, Error> {
+        let (mmap, permit) = io_manager.open_table_mmap(path)?;
+        let mut cache = Self::from_mmap(mmap, quota)?;
+        cache.io_permit = Some(permit);
+        Ok(cache)
     }
 
     pub(crate) fn get<Comp: Comparator>(&self, key: &InternalKey<Comp>) -> Option<Vec<u8>> {
-        if let Ok(idx) = self.catalog.binary_search_by(
-            |catalog_item| {
+        if let Some(bloom) = &self.bloom {
+            if !bloom.may_contain(key.user_key()) {
+                return None
+            }
+        }
+
+        let catalog = self.catalog.as_slice();
+
+        let idx = if let Some(hash_index) = &self.hash_index {
+            hash_index.lookup(key.user_key(), |candidate| {
+                let catalog_item = &catalog[candidate as usize];
                 let seq = catalog_item.key_seq;
                 let user_key = self.key(catalog_item);
                 let lookup_key = InternalKey::new(seq, UserKey::new_borrow(user_key));
-                // TODO this is buggy.
-                key.cmp(&lookup_key)
-            }) {
-            if self.catalog[idx].value_off & TABLE_DELETION_BITMASK != 0 {
-                None
-            } else {
-                Some(self.value(&self.catalog[idx]).to_vec())
-            }
+                key.cmp(&lookup_key) == KeyOrdering::Equal
+            }).map(|idx| idx as usize)
         } else {
-            None
+            catalog.binary_search_by(
+                |catalog_item| {
+                    let seq = catalog_item.key_seq;
+                    let user_key = self.key(catalog_item);
+                    let lookup_key = InternalKey::new(seq, UserKey::new_borrow(user_key));
+                    // TODO this is buggy.
+                    key.cmp(&lookup_key)
+                }).ok()
+        };
+
+        match idx {
+            Some(idx) if catalog[idx].value_off & TABLE_DELETION_BITMASK == 0 => Some(self.value(&catalog[idx]).to_vec()),
+            _ => None,
         }
     }
 
@@ -131,16 +655,183 @@ impl ScTableCache {
 
     pub(crate) fn nth_item(&self, n: usize) -> (u64, &[u8], &[u8]) {
         assert!(n < self.catalog_size());
-        let catalog_item = &self.catalog[n];
+        let catalog_item = &self.catalog.as_slice()[n];
         (catalog_item.key_seq, self.key(catalog_item), self.value(catalog_item))
     }
 
     fn key(&self, catalog_item: &ScTableCatalogItem) -> &[u8] {
-        &self.data[catalog_item.key_off as usize .. (catalog_item.key_off + catalog_item.key_len) as usize]
+        &self.data.as_slice()[catalog_item.key_off as usize .. (catalog_item.key_off + catalog_item.key_len) as usize]
     }
 
     fn value(&self, catalog_item: &ScTableCatalogItem) -> &[u8] {
-        &self.data[catalog_item.value_off as usize .. (catalog_item.value_off + catalog_item.value_len) as usize]
+        &self.data.as_slice()[catalog_item.value_off as usize .. (catalog_item.value_off + catalog_item.value_len) as usize]
+    }
+
+    /// Approximate resident footprint: the parsed catalog plus the data
+    /// region, used both for [`ScottDB::approximate_memory_usage`] and to
+    /// size demoted entries in the disk cache tier.
+    pub(crate) fn weight(&self) -> usize {
+        self.catalog.len() * TABLE_CATALOG_ITEM_SIZE + self.data.as_slice().len()
+    }
+
+    /// Serializes the already-validated, already-parsed representation for
+    /// the disk cache tier. Unlike the on-disk table format this carries no
+    /// CRC of its own: the memory tier only ever writes bytes it just
+    /// produced from a table that already passed `from_raw`/`from_mmap`'s
+    /// checks, so re-validating here would just burn CPU for no benefit.
+    fn to_disk_cache_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.weight() + 16);
+        out.extend_from_slice(&encode_fixed32_ret(self.catalog.len() as u32));
+        for item in self.catalog.as_slice() {
+            item.serialize(&mut out);
+        }
+        let data = self.data.as_slice();
+        out.extend_from_slice(&encode_fixed32_ret(data.len() as u32));
+        out.extend_from_slice(data);
+        match &self.bloom {
+            Some(bloom) => { out.push(1); bloom.serialize(&mut out); }
+            None => out.push(0),
+        }
+        match &self.hash_index {
+            Some(hash_index) => { out.push(1); hash_index.serialize(&mut out); }
+            None => out.push(0),
+        }
+        out
+    }
+
+    /// Parses the memory tier's own serialization of an already-validated
+    /// table, as written by `to_disk_cache_bytes`/`DiskTier::demote`. Unlike
+    /// that format's lack of a CRC (see `to_disk_cache_bytes`'s doc comment),
+    /// every length read here is still bounds-checked via `take_bytes`
+    /// before it drives a slice or allocation: a torn or truncated file
+    /// yields `Error::sc_table_corrupt`, not a panic.
+    fn from_disk_cache_bytes(bytes: &[u8], quota: CacheQuota) -> Result<Self, Error> {
+        let mut pos = 0usize;
+        let catalog_len = decode_fixed32(take_bytes(bytes, pos, 4)?) as usize;
+        pos += 4;
+        if catalog_len > TABLE_MAX_SIZE / TABLE_CATALOG_ITEM_SIZE {
+            return Err(Error::sc_table_corrupt("corrupt disk cache catalog length".into()))
+        }
+        let catalog_bytes_len = catalog_len * TABLE_CATALOG_ITEM_SIZE;
+        let items: &[ScTableCatalogItem] = bytemuck::try_cast_slice(take_bytes(bytes, pos, catalog_bytes_len)?)
+            .map_err(|_| Error::sc_table_corrupt("corrupt disk cache catalog".into()))?;
+        let catalog = CatalogData::Owned(try_clone_slice(items)?);
+        pos += catalog_bytes_len;
+
+        let data_len = decode_fixed32(take_bytes(bytes, pos, 4)?) as usize;
+        pos += 4;
+        if data_len > TABLE_MAX_SIZE {
+            return Err(Error::sc_table_corrupt("corrupt disk cache data length".into()))
+        }
+        let data = TableData::Owned(try_clone_slice(take_bytes(bytes, pos, data_len)?)?);
+        pos += data_len;
+
+        let has_bloom = take_bytes(bytes, pos, 1)?[0];
+        pos += 1;
+        let bloom = match has_bloom {
+            1 => {
+                let bloom_len = decode_fixed32(take_bytes(bytes, pos, 4)?) as usize;
+                if bloom_len > TABLE_MAX_SIZE {
+                    return Err(Error::sc_table_corrupt("corrupt disk cache bloom length".into()))
+                }
+                let bloom = TableBloomFilter::parse(take_bytes(bytes, pos, TABLE_BLOOM_FOOTER_SIZE + bloom_len)?)?;
+                pos += TABLE_BLOOM_FOOTER_SIZE + bloom_len;
+                bloom
+            }
+            0 => None,
+            _ => return Err(Error::sc_table_corrupt("corrupt disk cache bloom marker".into())),
+        };
+
+        let has_hash_index = take_bytes(bytes, pos, 1)?[0];
+        pos += 1;
+        let hash_index = match has_hash_index {
+            1 => {
+                take_bytes(bytes, pos, TABLE_HASH_INDEX_FOOTER_SIZE)?;
+                TableHashIndex::parse(&bytes[pos..])?
+            }
+            0 => None,
+            _ => return Err(Error::sc_table_corrupt("corrupt disk cache hash index marker".into())),
+        };
+        Ok(Self { catalog, data, bloom, hash_index, quota, io_permit: None })
+    }
+}
+
+/// Second, disk-backed cache tier. `TableCacheManager` demotes entries
+/// evicted from the in-memory LRU here instead of dropping them, so a
+/// later `get_cache` can reconstruct the already-validated, already-parsed
+/// `ScTableCache` straight from disk without re-running `from_raw`'s CRC
+/// and catalog-decode pass. Budgeted in bytes (`Options::disk_cache_bytes`)
+/// since, unlike the memory tier, a handful of large tables shouldn't
+/// count the same as a handful of small ones. The index of demoted entries
+/// is held in memory only: it starts empty, and files left in `dir` by an
+/// earlier process are not read back.
+struct DiskTier {
+    dir: PathBuf,
+    budget_bytes: usize,
+    index: Mutex<LruCache<ScTableFile, usize>>,
+    bytes_used: AtomicUsize,
+}
+
+impl DiskTier {
+    fn new(db_name: &str, budget_bytes: usize) -> Self {
+        let dir = PathBuf::from(db_name).join("table_cache");
+        let _ = fs::create_dir_all(&dir);
+        Self {
+            dir,
+            budget_bytes,
+            index: Mutex::new(LruCache::unbounded()),
+            bytes_used: AtomicUsize::new(0),
+        }
+    }
+
+    fn path_for(&self, table_file: ScTableFile) -> PathBuf {
+        self.dir.join(format!("{:?}.tcache", table_file))
+    }
+
+    /// Non-mutating containment check against the disk-tier index, so
+    /// `get_cache` can avoid acquiring a memory-tier quota slot for a table
+    /// that was never demoted in the first place.
+    fn contains(&self, table_file: ScTableFile) -> bool {
+        self.index.lock().unwrap().contains(&table_file)
+    }
+
+    /// Writes `bytes` to `final_path` via a sibling temp file plus a rename,
+    /// so a crash mid-write leaves the temp file orphaned rather than
+    /// truncating (or corrupting) whatever `final_path` already held, as a
+    /// plain `fs::write` would.
+    fn write_atomic(final_path: &std::path::Path, bytes: &[u8]) -> std::io::Result<()> {
+        let tmp_path = final_path.with_extension("tcache.tmp");
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, final_path)
+    }
+
+    fn demote(&self, table_file: ScTableFile, cache: &ScTableCache) {
+        let bytes = cache.to_disk_cache_bytes();
+        let weight = bytes.len();
+        if Self::write_atomic(&self.path_for(table_file), &bytes).is_err() {
+            return
+        }
+
+        let mut index = self.index.lock().unwrap();
+        index.put(table_file, weight);
+        let mut used = self.bytes_used.fetch_add(weight, Ordering::SeqCst) + weight;
+        while used > self.budget_bytes {
+            match index.pop_lru() {
+                Some((evicted_file, evicted_weight)) => {
+                    let _ = fs::remove_file(self.path_for(evicted_file));
+                    used = self.bytes_used.fetch_sub(evicted_weight, Ordering::SeqCst) - evicted_weight;
+                }
+                None => break,
+            }
+        }
+    }
+
+    fn promote(&self, table_file: ScTableFile, quota: CacheQuota) -> Option<ScTableCache> {
+        let weight = self.index.lock().unwrap().pop(&table_file)?;
+        let bytes = fs::read(self.path_for(table_file)).ok()?;
+        self.bytes_used.fetch_sub(weight, Ordering::SeqCst);
+        let _ = fs::remove_file(self.path_for(table_file));
+        ScTableCache::from_disk_cache_bytes(&bytes, quota).ok()
     }
 }
 
@@ -162,16 +853,20 @@ impl Drop for CacheQuota {
 
 pub(crate) struct TableCacheManager {
     lru: Mutex<LruCache<ScTableFile, Arc<ScTableCache>>>,
-    sem: Semaphore
+    cache_count: usize,
+    sem: Semaphore,
+    disk_tier: Option<DiskTier>,
 }
 
 /// Warning: make sure all `CacheQuota`s are dropped before the `TableCacheManager` drops.
 /// Maybe we should mark the TableCacheManager to be `unsafe`.
 impl TableCacheManager {
-    pub(crate) fn new(cache_count: usize) -> Self {
+    pub(crate) fn new(cache_count: usize, db_name: &str, disk_cache_bytes: usize) -> Self {
         TableCacheManager {
             lru: Mutex::new(LruCache::new(cache_count)),
-            sem: Semaphore::new(cache_count as isize)
+            cache_count,
+            sem: Semaphore::new(cache_count as isize),
+            disk_tier: if disk_cache_bytes > 0 { Some(DiskTier::new(db_name, disk_cache_bytes)) } else { None },
         }
     }
 
@@ -182,15 +877,305 @@ impl TableCacheManager {
 
     pub(crate) fn add_cache(&self, table_file: ScTableFile, table_cache: ScTableCache) -> Arc<ScTableCache> {
         let ret = Arc::new(table_cache);
-        self.lru.lock().unwrap().put(table_file, ret.clone());
+
+        let mut lru = self.lru.lock().unwrap();
+        if lru.len() >= self.cache_count && !lru.contains(&table_file) {
+            if let (Some(disk_tier), Some((evicted_file, evicted_cache))) = (&self.disk_tier, lru.pop_lru()) {
+                disk_tier.demote(evicted_file, &evicted_cache);
+            }
+        }
+        lru.put(table_file, ret.clone());
         ret
     }
 
     pub(crate) fn get_cache(&self, table_file: ScTableFile) -> Option<Arc<ScTableCache>> {
-        self.lru.lock().unwrap().get(&table_file).and_then(|arc| Some(arc.clone()))
+        if let Some(hit) = self.lru.lock().unwrap().get(&table_file).and_then(|arc| Some(arc.clone())) {
+            return Some(hit)
+        }
+
+        // Check disk-tier containment before paying for a memory-tier quota
+        // slot: under a fully-utilized quota, acquiring one just to learn
+        // `promote` has nothing to return would block this miss on readers
+        // holding unrelated tables, even though most in-memory misses were
+        // never demoted at all.
+        let disk_tier = self.disk_tier.as_ref()?;
+        if !disk_tier.contains(table_file) {
+            return None
+        }
+
+        let promoted = disk_tier.promote(table_file, self.acquire_quota())?;
+        Some(self.add_cache(table_file, promoted))
+    }
+
+    /// Non-blocking, read-only lookup against the in-memory tier only: no
+    /// quota semaphore acquisition, no disk-tier promotion, and no eviction
+    /// of another live table to make room. Unlike `get_cache`, a miss here
+    /// just means "not presently resident in memory" -- it does not attempt
+    /// to determine whether the table exists on the disk tier. Meant for
+    /// introspection callers ([`ScottDB::live_files`]) that must stay cheap
+    /// and side-effect-free rather than pay promotion cost for a read.
+    pub(crate) fn peek_cache(&self, table_file: ScTableFile) -> Option<Arc<ScTableCache>> {
+        self.lru.lock().unwrap().peek(&table_file).cloned()
+    }
+
+    /// Sum of the resident `ScTableCache` data+catalog bytes currently held
+    /// by the memory tier, for [`ScottDB::approximate_memory_usage`].
+    pub(crate) fn resident_bytes(&self) -> usize {
+        self.lru.lock().unwrap().iter().map(|(_, cache)| cache.weight()).sum()
     }
 
     fn on_cache_released(&self) {
         self.sem.release()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds the smallest valid table buffer `parse_ranges`/`from_raw`
+    /// will accept for the given catalog items and data payload: header,
+    /// catalog, codec-wrapped data region, empty bloom footer, empty hash
+    /// index footer, magic. Tests that need a malformed file start from
+    /// this and corrupt one field.
+    fn build_table_bytes(items: &[ScTableCatalogItem], payload: &[u8]) -> Vec<u8> {
+        let mut catalog_bytes = Vec::new();
+        for item in items {
+            item.serialize(&mut catalog_bytes);
+        }
+        let data = encode_data_region(payload, CompressionType::None);
+
+        let mut head = vec![0u8; TABLE_HEAD_SIZE];
+        head[0..4].copy_from_slice(&encode_fixed32_ret(catalog_bytes.len() as u32));
+        head[4..8].copy_from_slice(&encode_fixed32_ret(data.len() as u32));
+        head[8..12].copy_from_slice(&encode_fixed32_ret(crc32::checksum_ieee(&catalog_bytes)));
+        head[12..16].copy_from_slice(&encode_fixed32_ret(crc32::checksum_ieee(&data)));
+
+        let mut out = head;
+        out.extend_from_slice(&catalog_bytes);
+        out.extend_from_slice(&data);
+        out.extend_from_slice(&encode_fixed32_ret(0)); // bloom_len == 0
+        out.extend_from_slice(&encode_fixed32_ret(0)); // bloom_crc (unused when empty)
+        out.extend_from_slice(&encode_fixed32_ret(0)); // hash_index_len == 0
+        out.extend_from_slice(&encode_fixed32_ret(0)); // hash_index_crc (unused when empty)
+        out.extend_from_slice(TABLE_MAGIC);
+        out
+    }
+
+    #[test]
+    fn bloom_filter_has_no_false_negatives() {
+        let keys: Vec<&[u8]> = vec![b"alpha", b"bravo", b"charlie", b"delta", b"echo"];
+        let filter = TableBloomFilter::build(keys.iter().copied());
+        for key in &keys {
+            assert!(filter.may_contain(key), "bloom filter missed an inserted key: {:?}", key);
+        }
+    }
+
+    #[test]
+    fn bloom_filter_rejects_most_absent_keys() {
+        let present: Vec<[u8; 4]> = (0u32..200).map(|i| i.to_be_bytes()).collect();
+        let filter = TableBloomFilter::build(present.iter().map(|k| k.as_slice()));
+        let false_positives = (200u32..400)
+            .filter(|i| filter.may_contain(&i.to_be_bytes()))
+            .count();
+        // ~1% false-positive rate at BITS_PER_KEY == 10; generous slack to
+        // avoid test flakiness while still catching a badly broken hash.
+        assert!(false_positives < 20, "unexpectedly high false-positive rate: {false_positives}/200");
+    }
+
+    #[test]
+    fn parse_ranges_rejects_oversized_bloom_footer_length_without_panicking() {
+        let mut raw = build_table_bytes(&[], &[]);
+        // Empty hash index footer (8 bytes) sits between the magic and the
+        // empty bloom footer (8 bytes) in a minimal table built above.
+        let bloom_footer_end = raw.len() - TABLE_MAGIC_SIZE - TABLE_HASH_INDEX_FOOTER_SIZE;
+        let bloom_len_offset = bloom_footer_end - TABLE_BLOOM_FOOTER_SIZE;
+        raw[bloom_len_offset..bloom_len_offset + 4].copy_from_slice(&encode_fixed32_ret(u32::MAX));
+
+        let result = parse_ranges(&raw);
+        assert!(result.is_err(), "expected a corrupt-table error, not a successful parse");
+    }
+
+    #[test]
+    fn data_region_round_trips_uncompressed() {
+        let payload = b"some uncompressed table data".to_vec();
+        let encoded = encode_data_region(&payload, CompressionType::None);
+        match decode_data_region(&encoded).unwrap() {
+            DecodedDataRegion::Identity { payload_offset, len } => {
+                assert_eq!(&encoded[payload_offset..payload_offset + len], payload.as_slice());
+            }
+            DecodedDataRegion::Owned(_) => panic!("uncompressed region should decode as Identity"),
+        }
+    }
+
+    #[test]
+    fn data_region_round_trips_through_lz4() {
+        let payload = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaabbbbbbbbbbbb".to_vec();
+        let encoded = encode_data_region(&payload, CompressionType::Lz4);
+        match decode_data_region(&encoded).unwrap() {
+            DecodedDataRegion::Owned(buf) => assert_eq!(buf, payload),
+            DecodedDataRegion::Identity { .. } => panic!("lz4 region should decode as Owned"),
+        }
+    }
+
+    #[test]
+    fn data_region_rejects_declared_size_over_table_max_without_panicking() {
+        let mut header = vec![0u8; TABLE_DATA_CODEC_HEADER_SIZE];
+        header[0] = 1; // codec = lz4
+        header[4..8].copy_from_slice(&encode_fixed32_ret(u32::MAX));
+        let result = decode_data_region(&header);
+        assert!(result.is_err(), "expected a corrupt-table error, not an oversized allocation attempt");
+    }
+
+    #[test]
+    fn hash_index_finds_every_key_at_its_catalog_index() {
+        let keys: Vec<Vec<u8>> = (0u32..500).map(|i| format!("key-{i}").into_bytes()).collect();
+        let index = TableHashIndex::build(keys.iter().map(|k| k.as_slice()));
+
+        for (catalog_idx, key) in keys.iter().enumerate() {
+            let found = index.lookup(key, |candidate| candidate as usize == catalog_idx);
+            assert_eq!(found, Some(catalog_idx as u32), "lookup failed for {:?}", key);
+        }
+    }
+
+    #[test]
+    fn hash_index_lookup_misses_an_absent_key() {
+        let keys: Vec<Vec<u8>> = (0u32..50).map(|i| format!("key-{i}").into_bytes()).collect();
+        let index = TableHashIndex::build(keys.iter().map(|k| k.as_slice()));
+        let found = index.lookup(b"not-present", |candidate| keys[candidate as usize].as_slice() == b"not-present");
+        assert_eq!(found, None);
+    }
+
+    #[test]
+    fn parse_ranges_rejects_oversized_hash_index_footer_length_without_panicking() {
+        let mut raw = build_table_bytes(&[], &[]);
+        let hash_index_footer_end = raw.len() - TABLE_MAGIC_SIZE;
+        let hash_index_len_offset = hash_index_footer_end - TABLE_HASH_INDEX_FOOTER_SIZE;
+        raw[hash_index_len_offset..hash_index_len_offset + 4].copy_from_slice(&encode_fixed32_ret(u32::MAX));
+
+        // Before the chunk0-6 fix this underflowed the bloom_footer_end
+        // subtraction instead of returning an error.
+        let result = parse_ranges(&raw);
+        assert!(result.is_err(), "expected a corrupt-table error, not a successful parse");
+    }
+
+    #[test]
+    fn disk_cache_bytes_round_trip() {
+        let manager = TableCacheManager::new(4, "chunk0-4-test-db", 0);
+        let item = ScTableCatalogItem::new(1, 0, 5, 5, 5);
+        let raw = build_table_bytes(&[item], b"helloworld");
+
+        let original = ScTableCache::from_raw(&raw, manager.acquire_quota()).unwrap();
+        let bytes = original.to_disk_cache_bytes();
+        let restored = ScTableCache::from_disk_cache_bytes(&bytes, manager.acquire_quota()).unwrap();
+
+        assert_eq!(restored.catalog_size(), original.catalog_size());
+        assert_eq!(restored.weight(), original.weight());
+        let (seq, key, value) = restored.nth_item(0);
+        assert_eq!((seq, key, value), (1, b"hello".as_slice(), b"world".as_slice()));
+    }
+
+    #[test]
+    fn disk_cache_bytes_rejects_truncated_input_without_panicking() {
+        let manager = TableCacheManager::new(4, "chunk0-4-test-db-truncated", 0);
+        let item = ScTableCatalogItem::new(1, 0, 5, 5, 5);
+        let raw = build_table_bytes(&[item], b"helloworld");
+
+        let cache = ScTableCache::from_raw(&raw, manager.acquire_quota()).unwrap();
+        let bytes = cache.to_disk_cache_bytes();
+
+        // Simulate a crash mid-write leaving a torn disk-cache file: every
+        // non-empty truncation should error, never panic.
+        for cut in 1..bytes.len() {
+            let torn = &bytes[..bytes.len() - cut];
+            let result = ScTableCache::from_disk_cache_bytes(torn, manager.acquire_quota());
+            assert!(result.is_err(), "truncating to {} bytes should error, not panic", torn.len());
+        }
+    }
+
+    #[test]
+    fn catalog_items_rejects_near_overflow_offsets_without_panicking() {
+        // Before the chunk0-8 audit fix, `key_off + key_len` summed as u32
+        // and panicked on overflow-checked builds instead of returning this
+        // error.
+        let item = ScTableCatalogItem::new(1, u32::MAX - 1, u32::MAX - 1, 0, 1);
+        let mut catalog_bytes = Vec::new();
+        item.serialize(&mut catalog_bytes);
+
+        let result = catalog_items(&catalog_bytes, 10);
+        assert!(result.is_err(), "expected a corrupt-table error, not a successful parse or a panic");
+    }
+
+    #[test]
+    fn peek_cache_hits_the_memory_tier_and_misses_everything_else() {
+        let manager = TableCacheManager::new(4, "chunk0-7-test-db", 0);
+        let cached_file = ScTableFile(1);
+        let uncached_file = ScTableFile(2);
+
+        let item = ScTableCatalogItem::new(1, 0, 5, 5, 5);
+        let raw = build_table_bytes(&[item], b"helloworld");
+        let cache = ScTableCache::from_raw(&raw, manager.acquire_quota()).unwrap();
+        manager.add_cache(cached_file, cache);
+
+        let hit = manager.peek_cache(cached_file);
+        assert!(hit.is_some(), "peek_cache should hit a table just added to the memory tier");
+
+        let miss = manager.peek_cache(uncached_file);
+        assert!(miss.is_none(), "peek_cache should miss a table never added to any tier");
+    }
+
+    #[test]
+    fn resident_bytes_counts_only_the_memory_tier() {
+        let manager = TableCacheManager::new(4, "chunk0-7-test-db-resident", 0);
+        assert_eq!(manager.resident_bytes(), 0);
+
+        let item = ScTableCatalogItem::new(1, 0, 5, 5, 5);
+        let raw = build_table_bytes(&[item], b"helloworld");
+        let cache = ScTableCache::from_raw(&raw, manager.acquire_quota()).unwrap();
+        let expected_weight = cache.weight();
+        manager.add_cache(ScTableFile(1), cache);
+
+        assert_eq!(manager.resident_bytes(), expected_weight);
+    }
+
+    fn unique_temp_path(label: &str) -> std::path::PathBuf {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        std::env::temp_dir().join(format!("scottdb-test-{}-{}-{}.sct", std::process::id(), label, id))
+    }
+
+    #[test]
+    fn open_mmap_round_trips_a_real_file_through_io_manager() {
+        let path = unique_temp_path("open-mmap");
+        let item = ScTableCatalogItem::new(1, 0, 5, 5, 5);
+        let raw = build_table_bytes(&[item], b"helloworld");
+        fs::write(&path, &raw).unwrap();
+
+        let io_manager = IOManager::new(1);
+        let cache_manager = TableCacheManager::new(4, "chunk0-3-test-db", 0);
+        let cache = ScTableCache::open_mmap(&path, &io_manager, cache_manager.acquire_quota()).unwrap();
+
+        let (seq, key, value) = cache.nth_item(0);
+        assert_eq!((seq, key, value), (1, b"hello".as_slice(), b"world".as_slice()));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn io_manager_releases_its_slot_when_the_permit_drops() {
+        let path = unique_temp_path("io-manager-slot");
+        fs::write(&path, build_table_bytes(&[], &[])).unwrap();
+
+        let io_manager = IOManager::new(1);
+        let (_, first_permit) = io_manager.open_table_mmap(&path).unwrap();
+        drop(first_permit);
+
+        // With only one slot, this would block forever if the first
+        // permit's drop hadn't released it.
+        let (_, second_permit) = io_manager.open_table_mmap(&path).unwrap();
+        drop(second_permit);
+
+        let _ = fs::remove_file(&path);
+    }
+}